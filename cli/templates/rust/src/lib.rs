@@ -1,7 +1,7 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
 
 // Import the `console.log` function from the `console` module
 #[wasm_bindgen]
@@ -16,7 +16,7 @@ macro_rules! console_log {
 }
 
 // AO Message structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AOMessage {
     #[serde(rename = "Id")]
     pub id: Option<String>,
@@ -73,58 +73,458 @@ impl AOResponse {
     }
 }
 
-// Global state management
-static STATE: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+// An outbound message a handler wants the host scheduler to relay on
+// this process's behalf, distinct from the single `AOResponse` sent
+// back to the triggering message's sender.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboundMessage {
+    #[serde(rename = "Target")]
+    pub target: String,
+    #[serde(rename = "Tags")]
+    pub tags: HashMap<String, String>,
+    #[serde(rename = "Data")]
+    pub data: String,
+}
+
+impl OutboundMessage {
+    pub fn new(target: &str, tags: HashMap<String, String>, data: &str) -> Self {
+        Self {
+            target: target.to_string(),
+            tags,
+            data: data.to_string(),
+        }
+    }
+}
+
+// A request to spawn a new AO process, relayed by the host alongside any
+// outbound messages produced while handling the same incoming message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpawnRequest {
+    #[serde(rename = "Module")]
+    pub module: String,
+    #[serde(rename = "Tags")]
+    pub tags: HashMap<String, String>,
+    #[serde(rename = "Data")]
+    pub data: Option<String>,
+}
+
+impl SpawnRequest {
+    pub fn new(module: &str, tags: HashMap<String, String>, data: Option<&str>) -> Self {
+        Self {
+            module: module.to_string(),
+            tags,
+            data: data.map(|d| d.to_string()),
+        }
+    }
+}
+
+// Accumulates outbound messages and spawn requests produced while a
+// handler runs. Handlers receive this by mutable reference so they can
+// emit any number of side effects instead of a single reply.
+#[derive(Debug, Default)]
+pub struct Outbox {
+    messages: Vec<OutboundMessage>,
+    spawns: Vec<SpawnRequest>,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn send(&mut self, target: &str, tags: HashMap<String, String>, data: &str) {
+        self.messages.push(OutboundMessage::new(target, tags, data));
+    }
+
+    pub fn spawn(&mut self, module: &str, tags: HashMap<String, String>, data: Option<&str>) {
+        self.spawns.push(SpawnRequest::new(module, tags, data));
+    }
+}
+
+// The full outcome of handling one incoming message: the reply sent
+// back to the sender (if any), plus any messages and spawns the handler
+// accumulated in its `Outbox` along the way.
+#[derive(Debug, Default)]
+pub struct HandlerResult {
+    pub output: Option<AOResponse>,
+    pub messages: Vec<OutboundMessage>,
+    pub spawns: Vec<SpawnRequest>,
+}
+
+impl HandlerResult {
+    pub fn new(output: Option<AOResponse>, outbox: Outbox) -> Self {
+        Self {
+            output,
+            messages: outbox.messages,
+            spawns: outbox.spawns,
+        }
+    }
+}
+
+// The wasm-facing envelope that carries a `HandlerResult` across the
+// boundary as JSON, so the host scheduler can relay `Messages` and
+// `Spawns` in addition to delivering `Output` back to the sender.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    #[serde(rename = "Output")]
+    pub output: Option<AOResponse>,
+    #[serde(rename = "Messages")]
+    pub messages: Vec<OutboundMessage>,
+    #[serde(rename = "Spawns")]
+    pub spawns: Vec<SpawnRequest>,
+}
+
+impl From<HandlerResult> for Envelope {
+    fn from(result: HandlerResult) -> Self {
+        Self {
+            output: result.output,
+            messages: result.messages,
+            spawns: result.spawns,
+        }
+    }
+}
+
+impl Envelope {
+    pub fn from_output(output: AOResponse) -> Self {
+        Self {
+            output: Some(output),
+            messages: Vec::new(),
+            spawns: Vec::new(),
+        }
+    }
+}
+
+// Namespace every process uses when it doesn't specify one, keeping the
+// common case (a single flat keyspace) as easy as before namespacing.
+const DEFAULT_NAMESPACE: &str = "default";
+const DEFAULT_MAX_ENTRIES: usize = 64;
+const DEFAULT_MAX_VALUE_LEN: usize = 1000;
+
+// Global state management, partitioned by namespace so a process can
+// isolate data domains (e.g. `users`, `config`, `cache`) from one
+// another. Values are structured JSON rather than bare strings so a
+// process can store numbers, booleans, and nested objects natively. Each
+// entry carries its own optional expiration, expressed as the
+// `msg.timestamp` value (AO's deterministic clock) at which it stops
+// being visible.
+// Namespace name -> (key -> (value, optional expiry)).
+type NamespaceStore = HashMap<String, HashMap<String, (serde_json::Value, Option<u64>)>>;
+
+// `HashMap::new`/`HashSet::new` aren't `const fn`, so these statics need
+// `LazyLock` rather than a plain `Mutex::new(...)` initializer.
+static STATE: LazyLock<Mutex<NamespaceStore>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Reserved, separate from user data, so it can never be overwritten via
+// Set/Remove/Clear. Holds the set of addresses allowed to perform
+// mutating actions.
+static ADMINS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+// Size budget for a namespace. Namespaces without an explicit entry here
+// fall back to `DEFAULT_MAX_ENTRIES`/`DEFAULT_MAX_VALUE_LEN`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct NamespaceLimits {
+    pub max_entries: usize,
+    pub max_value_len: usize,
+}
+
+impl Default for NamespaceLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_value_len: DEFAULT_MAX_VALUE_LEN,
+        }
+    }
+}
+
+static NAMESPACE_LIMITS: LazyLock<Mutex<HashMap<String, NamespaceLimits>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Bumped whenever `StateSnapshot`'s shape changes, so `restore` can tell
+// an old snapshot apart from a corrupt one and, in the future, migrate
+// rather than fail outright. Bumped to 2 when `Admins` was added, since
+// a restore that dropped the admin set would leave it empty, locking
+// every sender out of mutating actions until `set_owner` seeds a new one.
+const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+// The versioned, wasm-boundary-crossing form of everything `ProcessState`
+// persists, used to checkpoint a process before it's re-instantiated and
+// rehydrate it afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StateSnapshot {
+    #[serde(rename = "Version")]
+    version: u32,
+    #[serde(rename = "Namespaces")]
+    namespaces: NamespaceStore,
+    #[serde(rename = "NamespaceLimits")]
+    namespace_limits: HashMap<String, NamespaceLimits>,
+    // Defaults to empty so a version-1 blob (predating this field) restores
+    // cleanly instead of failing on a generic "missing field" error --
+    // version 1 never persisted admins either, so an empty set here just
+    // matches what it already meant to lose them.
+    #[serde(rename = "Admins", default)]
+    admins: HashSet<String>,
+}
 
 // Process state management
 pub struct ProcessState;
 
 impl ProcessState {
-    pub fn set(key: &str, value: &str) -> Result<(), String> {
+    // Seeds the admin set from the process's true owner, a no-op once at
+    // least one admin exists. Only called from the `set_owner` wasm
+    // export at instantiation time (the one place the host can supply
+    // an already-verified owner) -- never from a handled message, since
+    // `msg.owner` is attacker-controlled and whichever sender's message
+    // happened to be processed first would otherwise win permanent control
+    // of the process.
+    pub fn bootstrap_owner(owner: &str) -> Result<(), String> {
+        let mut admins = ADMINS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        if admins.is_empty() {
+            admins.insert(owner.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn is_admin(address: &str) -> Result<bool, String> {
+        let admins = ADMINS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        Ok(admins.contains(address))
+    }
+
+    pub fn add_admin(address: &str) -> Result<(), String> {
+        let mut admins = ADMINS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        admins.insert(address.to_string());
+        Ok(())
+    }
+
+    // Refuses to remove the sole remaining admin, so the process can never
+    // be left with an empty admin set -- which would lock every sender out
+    // of mutating actions until the next `set_owner` call seeds a new admin.
+    pub fn remove_admin(address: &str) -> Result<bool, String> {
+        let mut admins = ADMINS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        if admins.len() == 1 && admins.contains(address) {
+            return Err("Cannot remove the last remaining admin".to_string());
+        }
+        Ok(admins.remove(address))
+    }
+
+    #[cfg(test)]
+    fn reset_admins_for_test() -> Result<(), String> {
+        let mut admins = ADMINS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        admins.clear();
+        Ok(())
+    }
+
+    // Namespace-level size budget. Falls back to the default limits when
+    // the namespace hasn't been explicitly configured.
+    pub fn namespace_limits(namespace: &str) -> Result<NamespaceLimits, String> {
+        let limits = NAMESPACE_LIMITS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        Ok(limits.get(namespace).copied().unwrap_or_default())
+    }
+
+    pub fn configure_namespace(namespace: &str, max_entries: usize, max_value_len: usize) -> Result<(), String> {
+        let mut limits = NAMESPACE_LIMITS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        limits.insert(namespace.to_string(), NamespaceLimits { max_entries, max_value_len });
+        Ok(())
+    }
+
+    pub fn list_namespaces() -> Result<Vec<String>, String> {
+        let state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
+        let mut namespaces: Vec<String> = state.keys().cloned().collect();
+        namespaces.sort();
+        Ok(namespaces)
+    }
+
+    pub fn set(namespace: &str, key: &str, value: serde_json::Value, expires_at: Option<u64>) -> Result<(), String> {
         if key.is_empty() || key.len() > 64 {
             return Err("Key must be between 1 and 64 characters".to_string());
         }
 
-        if value.len() > 1000 {
-            return Err("Value must be less than 1000 characters".to_string());
+        let limits = Self::namespace_limits(namespace)?;
+        // Plain strings are measured by their own length, everything else
+        // by the length of its JSON serialization.
+        let value_len = match &value {
+            serde_json::Value::String(s) => s.len(),
+            other => serde_json::to_string(other)
+                .map_err(|e| format!("JSON serialization error: {}", e))?
+                .len(),
+        };
+        if value_len > limits.max_value_len {
+            return Err(format!("Value must be less than {} characters", limits.max_value_len));
         }
 
         let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
-        state.insert(key.to_string(), value.to_string());
+        let bucket = state.entry(namespace.to_string()).or_default();
+        if !bucket.contains_key(key) && bucket.len() >= limits.max_entries {
+            return Err(format!("Namespace '{}' has reached its limit of {} entries", namespace, limits.max_entries));
+        }
+        bucket.insert(key.to_string(), (value, expires_at));
         Ok(())
     }
 
-    pub fn get(key: &str) -> Result<Option<String>, String> {
+    // Reads a key as of `now`, lazily pruning it first if it has expired.
+    // `now` must come from the triggering message's `Timestamp`, never a
+    // system clock, so that replaying the same messages on any node
+    // produces the same state.
+    pub fn get_at(namespace: &str, key: &str, now: u64) -> Result<Option<serde_json::Value>, String> {
+        let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
+        let Some(bucket) = state.get_mut(namespace) else {
+            return Ok(None);
+        };
+        match bucket.get(key) {
+            Some((_, Some(expires_at))) if *expires_at <= now => {
+                bucket.remove(key);
+                Ok(None)
+            }
+            Some((value, _)) => Ok(Some(value.clone())),
+            None => Ok(None),
+        }
+    }
+
+    // Lists all keys in `namespace` visible as of `now`, lazily pruning
+    // any that have expired along the way.
+    pub fn list_at(namespace: &str, now: u64) -> Result<HashMap<String, serde_json::Value>, String> {
+        let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
+        let Some(bucket) = state.get_mut(namespace) else {
+            return Ok(HashMap::new());
+        };
+        bucket.retain(|_, (_, expires_at)| expires_at.is_none_or(|exp| exp > now));
+        Ok(bucket.iter().map(|(k, (v, _))| (k.clone(), v.clone())).collect())
+    }
+
+    // Atomically adds `delta` to a numeric-typed key and returns the new
+    // value, creating the key (starting from zero) if it doesn't exist
+    // yet. The read-modify-write happens under a single lock acquisition
+    // so concurrent Increment/Decrement calls never race.
+    pub fn increment(namespace: &str, key: &str, delta: i64, now: u64) -> Result<serde_json::Value, String> {
+        let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
+        let bucket = state.entry(namespace.to_string()).or_default();
+
+        if let Some((_, Some(expires_at))) = bucket.get(key) {
+            if *expires_at <= now {
+                bucket.remove(key);
+            }
+        }
+
+        let (current, expires_at) = match bucket.get(key) {
+            Some((value, expires_at)) => {
+                let n = value.as_i64()
+                    .ok_or_else(|| format!("Key '{}' is not an integer value", key))?;
+                (n, *expires_at)
+            }
+            None => (0, None),
+        };
+
+        let sum = current.checked_add(delta).ok_or_else(|| format!("Key '{}' would overflow on increment", key))?;
+        let updated = serde_json::Value::from(sum);
+        bucket.insert(key.to_string(), (updated.clone(), expires_at));
+        Ok(updated)
+    }
+
+    pub fn size_at(namespace: &str, now: u64) -> Result<usize, String> {
         let state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
-        Ok(state.get(key).cloned())
+        Ok(state.get(namespace)
+            .map(|bucket| bucket.values().filter(|(_, expires_at)| expires_at.is_none_or(|exp| exp > now)).count())
+            .unwrap_or(0))
     }
 
-    pub fn list() -> Result<HashMap<String, String>, String> {
+    // Total live entries across every namespace, as of `now`.
+    pub fn total_size_at(now: u64) -> Result<usize, String> {
         let state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
-        Ok(state.clone())
+        Ok(state.values()
+            .flat_map(|bucket| bucket.values())
+            .filter(|(_, expires_at)| expires_at.is_none_or(|exp| exp > now))
+            .count())
+    }
+
+    // Drops every entry, in every namespace, whose expiration has passed
+    // as of `now`. Called at the top of every handled message so expired
+    // entries disappear deterministically rather than on a timer.
+    pub fn purge_expired(now: u64) -> Result<usize, String> {
+        let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
+        let mut purged = 0;
+        for bucket in state.values_mut() {
+            let before = bucket.len();
+            bucket.retain(|_, (_, expires_at)| expires_at.is_none_or(|exp| exp > now));
+            purged += before - bucket.len();
+        }
+        Ok(purged)
     }
 
-    pub fn remove(key: &str) -> Result<bool, String> {
+    pub fn remove(namespace: &str, key: &str) -> Result<bool, String> {
         let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
-        Ok(state.remove(key).is_some())
+        Ok(state.get_mut(namespace).map(|bucket| bucket.remove(key).is_some()).unwrap_or(false))
+    }
+
+    // Wipes a single namespace, used by the `Clear` action.
+    pub fn clear(namespace: &str) -> Result<(), String> {
+        let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
+        state.remove(namespace);
+        Ok(())
     }
 
-    pub fn clear() -> Result<(), String> {
+    // Wipes every namespace, used by the wasm `clear_state` export and
+    // test setup that wants a clean slate regardless of namespace.
+    pub fn clear_all() -> Result<(), String> {
         let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
         state.clear();
         Ok(())
     }
 
-    pub fn size() -> Result<usize, String> {
-        let state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
-        Ok(state.len())
+    // Serializes every namespace (including TTL metadata and
+    // per-namespace limits) and the admin set to a versioned JSON blob a
+    // host can persist and later `restore` into a freshly-instantiated
+    // module. The admin set must travel with the rest of the state: a
+    // restore that dropped it would leave `ADMINS` empty, so no sender
+    // could perform a mutating action until `set_owner` seeds a new admin.
+    pub fn snapshot() -> String {
+        let namespaces = STATE.lock().map(|state| state.clone()).unwrap_or_default();
+        let namespace_limits = NAMESPACE_LIMITS.lock().map(|limits| limits.clone()).unwrap_or_default();
+        let admins = ADMINS.lock().map(|admins| admins.clone()).unwrap_or_default();
+
+        let snapshot = StateSnapshot {
+            version: SNAPSHOT_FORMAT_VERSION,
+            namespaces,
+            namespace_limits,
+            admins,
+        };
+
+        serde_json::to_string(&snapshot).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    // Loads a blob produced by `snapshot`, replacing all current
+    // namespaces, namespace limits, and admins. Rejects blobs from a
+    // future format version rather than guessing at their shape; a
+    // version-1 blob (predating `Admins`) is accepted and defaults its
+    // admin set to empty via `StateSnapshot`'s `#[serde(default)]`.
+    pub fn restore(blob: &str) -> Result<(), String> {
+        let snapshot: StateSnapshot = serde_json::from_str(blob)
+            .map_err(|e| format!("Invalid snapshot: {}", e))?;
+
+        if snapshot.version > SNAPSHOT_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported snapshot version {} (this build supports up to {})",
+                snapshot.version, SNAPSHOT_FORMAT_VERSION
+            ));
+        }
+
+        let mut state = STATE.lock().map_err(|e| format!("State lock error: {}", e))?;
+        *state = snapshot.namespaces;
+
+        let mut limits = NAMESPACE_LIMITS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        *limits = snapshot.namespace_limits;
+
+        let mut admins = ADMINS.lock().map_err(|e| format!("State lock error: {}", e))?;
+        *admins = snapshot.admins;
+
+        Ok(())
     }
 }
 
-// Message handlers
-pub fn handle_info(msg: &AOMessage) -> Result<AOResponse, String> {
+// Message handlers. `now` is the deterministic clock derived from the
+// triggering message's `Timestamp` (see `parse_timestamp`), used for TTL
+// expiry instead of a system clock.
+pub fn handle_info(msg: &AOMessage, now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
     let from = msg.from.as_deref().unwrap_or("unknown");
-    let state_size = ProcessState::size()?;
+    let state_size = ProcessState::total_size_at(now)?;
 
     let data = format!(
         "Hello from AO Process (Rust)! State entries: {}",
@@ -134,8 +534,9 @@ pub fn handle_info(msg: &AOMessage) -> Result<AOResponse, String> {
     Ok(AOResponse::new(from, "Info-Response", &data))
 }
 
-pub fn handle_set(msg: &AOMessage) -> Result<AOResponse, String> {
+pub fn handle_set(msg: &AOMessage, now: u64, outbox: &mut Outbox) -> Result<AOResponse, String> {
     let from = msg.from.as_deref().unwrap_or("unknown");
+    let namespace = namespace_of(msg)?;
 
     let key = msg.tags
         .as_ref()
@@ -149,46 +550,102 @@ pub fn handle_set(msg: &AOMessage) -> Result<AOResponse, String> {
         return Ok(AOResponse::error(from, "Invalid key format. Use alphanumeric characters, underscores, and hyphens only"));
     }
 
-    ProcessState::set(key, value)?;
+    let type_tag = msg.tags.as_ref().and_then(|tags| tags.get("Type")).map(|t| t.as_str());
+    let parsed_value = match parse_typed_value(value, type_tag) {
+        Ok(v) => v,
+        Err(e) => return Ok(AOResponse::error(from, &e)),
+    };
+
+    let expires_at = match msg.tags.as_ref().and_then(|tags| tags.get("Ttl")) {
+        Some(ttl) => {
+            let ttl_seconds: u64 = ttl.parse()
+                .map_err(|_| "Ttl must be a non-negative integer number of seconds".to_string())?;
+            Some(now.checked_add(ttl_seconds).ok_or("Ttl is too large and would overflow the expiry clock")?)
+        }
+        None => None,
+    };
+
+    ProcessState::set(&namespace, key, parsed_value, expires_at)?;
+
+    // Optionally relay the new value on to another process, e.g. a
+    // subscriber watching this key.
+    if let Some(forward_to) = msg.tags.as_ref().and_then(|tags| tags.get("Forward")) {
+        let mut tags = HashMap::new();
+        tags.insert("Key".to_string(), key.clone());
+        outbox.send(forward_to, tags, value);
+    }
 
     let data = format!("Successfully set {} to {}", key, value);
     Ok(AOResponse::new(from, "Set-Response", &data))
 }
 
-pub fn handle_get(msg: &AOMessage) -> Result<AOResponse, String> {
+pub fn handle_get(msg: &AOMessage, now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
     let from = msg.from.as_deref().unwrap_or("unknown");
+    let namespace = namespace_of(msg)?;
 
     let key = msg.tags
         .as_ref()
         .and_then(|tags| tags.get("Key"))
         .ok_or("Key is required")?;
 
-    let value = ProcessState::get(key)?
-        .unwrap_or_else(|| "Not found".to_string());
+    let value = match ProcessState::get_at(&namespace, key, now)? {
+        Some(value) => value_to_response_string(&value)?,
+        None => "Not found".to_string(),
+    };
 
     Ok(AOResponse::new(from, "Get-Response", &value)
         .with_field("Key", key))
 }
 
-pub fn handle_list(msg: &AOMessage) -> Result<AOResponse, String> {
+pub fn handle_list(msg: &AOMessage, now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
     let from = msg.from.as_deref().unwrap_or("unknown");
+    let namespace = namespace_of(msg)?;
 
-    let state = ProcessState::list()?;
+    let state = ProcessState::list_at(&namespace, now)?;
     let state_json = serde_json::to_string(&state)
         .map_err(|e| format!("JSON serialization error: {}", e))?;
 
     Ok(AOResponse::new(from, "List-Response", &state_json))
 }
 
-pub fn handle_remove(msg: &AOMessage) -> Result<AOResponse, String> {
+pub fn handle_increment(msg: &AOMessage, now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
+    handle_counter_delta(msg, now, "Increment-Response", 1)
+}
+
+pub fn handle_decrement(msg: &AOMessage, now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
+    handle_counter_delta(msg, now, "Decrement-Response", -1)
+}
+
+fn handle_counter_delta(msg: &AOMessage, now: u64, response_action: &str, sign: i64) -> Result<AOResponse, String> {
     let from = msg.from.as_deref().unwrap_or("unknown");
+    let namespace = namespace_of(msg)?;
 
     let key = msg.tags
         .as_ref()
         .and_then(|tags| tags.get("Key"))
         .ok_or("Key is required")?;
 
-    let removed = ProcessState::remove(key)?;
+    let amount: i64 = match msg.tags.as_ref().and_then(|tags| tags.get("Amount")) {
+        Some(v) => v.parse().map_err(|_| "Amount must be an integer".to_string())?,
+        None => 1,
+    };
+
+    let new_value = ProcessState::increment(&namespace, key, sign * amount, now)?;
+    let data = value_to_response_string(&new_value)?;
+
+    Ok(AOResponse::new(from, response_action, &data).with_field("Key", key))
+}
+
+pub fn handle_remove(msg: &AOMessage, _now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
+    let from = msg.from.as_deref().unwrap_or("unknown");
+    let namespace = namespace_of(msg)?;
+
+    let key = msg.tags
+        .as_ref()
+        .and_then(|tags| tags.get("Key"))
+        .ok_or("Key is required")?;
+
+    let removed = ProcessState::remove(&namespace, key)?;
 
     let data = if removed {
         format!("Successfully removed {}", key)
@@ -199,44 +656,268 @@ pub fn handle_remove(msg: &AOMessage) -> Result<AOResponse, String> {
     Ok(AOResponse::new(from, "Remove-Response", &data))
 }
 
-pub fn handle_clear(msg: &AOMessage) -> Result<AOResponse, String> {
+pub fn handle_clear(msg: &AOMessage, _now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
+    let from = msg.from.as_deref().unwrap_or("unknown");
+    let namespace = namespace_of(msg)?;
+
+    ProcessState::clear(&namespace)?;
+
+    Ok(AOResponse::new(from, "Clear-Response", &format!("Namespace '{}' cleared successfully", namespace)))
+}
+
+pub fn handle_list_namespaces(msg: &AOMessage, _now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
+    let from = msg.from.as_deref().unwrap_or("unknown");
+
+    let namespaces = ProcessState::list_namespaces()?;
+    let data = serde_json::to_string(&namespaces)
+        .map_err(|e| format!("JSON serialization error: {}", e))?;
+
+    Ok(AOResponse::new(from, "ListNamespaces-Response", &data))
+}
+
+pub fn handle_configure_namespace(msg: &AOMessage, _now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
     let from = msg.from.as_deref().unwrap_or("unknown");
+    let namespace = namespace_of(msg)?;
+
+    let max_entries: usize = match msg.tags.as_ref().and_then(|tags| tags.get("MaxEntries")) {
+        Some(v) => v.parse().map_err(|_| "MaxEntries must be a non-negative integer".to_string())?,
+        None => DEFAULT_MAX_ENTRIES,
+    };
+    let max_value_len: usize = match msg.tags.as_ref().and_then(|tags| tags.get("MaxValueLen")) {
+        Some(v) => v.parse().map_err(|_| "MaxValueLen must be a non-negative integer".to_string())?,
+        None => DEFAULT_MAX_VALUE_LEN,
+    };
+
+    ProcessState::configure_namespace(&namespace, max_entries, max_value_len)?;
+
+    let data = format!(
+        "Namespace '{}' configured with max_entries={}, max_value_len={}",
+        namespace, max_entries, max_value_len
+    );
+    Ok(AOResponse::new(from, "ConfigureNamespace-Response", &data))
+}
+
+pub fn handle_add_admin(msg: &AOMessage, _now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
+    let from = msg.from.as_deref().unwrap_or("unknown");
+
+    let address = msg.tags
+        .as_ref()
+        .and_then(|tags| tags.get("Address"))
+        .ok_or("Address is required")?;
+
+    ProcessState::add_admin(address)?;
+
+    Ok(AOResponse::new(from, "AddAdmin-Response", &format!("Added admin {}", address)))
+}
+
+pub fn handle_remove_admin(msg: &AOMessage, _now: u64, _outbox: &mut Outbox) -> Result<AOResponse, String> {
+    let from = msg.from.as_deref().unwrap_or("unknown");
+
+    let address = msg.tags
+        .as_ref()
+        .and_then(|tags| tags.get("Address"))
+        .ok_or("Address is required")?;
+
+    let removed = ProcessState::remove_admin(address)?;
+
+    let data = if removed {
+        format!("Removed admin {}", address)
+    } else {
+        format!("{} was not an admin", address)
+    };
+
+    Ok(AOResponse::new(from, "RemoveAdmin-Response", &data))
+}
+
+// Actions gated to the authorized admin set; everything else (Info, Get,
+// List, ListNamespaces) stays open to any sender.
+const MUTATING_ACTIONS: &[&str] = &["Set", "Remove", "Clear", "AddAdmin", "RemoveAdmin", "ConfigureNamespace", "Increment", "Decrement"];
+
+// The namespace a message targets: the `Namespace` tag when present,
+// falling back to `DEFAULT_NAMESPACE`, validated with the same format
+// rules as keys so it can be used as a map key without surprises.
+fn namespace_of(msg: &AOMessage) -> Result<String, String> {
+    let namespace = msg.tags
+        .as_ref()
+        .and_then(|tags| tags.get("Namespace"))
+        .map(|n| n.as_str())
+        .unwrap_or(DEFAULT_NAMESPACE);
+
+    if namespace.is_empty() || namespace.len() > 64
+        || !namespace.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err("Invalid namespace format. Use alphanumeric characters, underscores, and hyphens only".to_string());
+    }
+
+    Ok(namespace.to_string())
+}
+
+// Parses a `Set` action's raw `Data` string into a structured value
+// according to its `Type` tag (`string|number|bool|json`), defaulting to
+// `string` so untagged callers keep behaving exactly as before
+// structured values were introduced.
+fn parse_typed_value(data: &str, type_tag: Option<&str>) -> Result<serde_json::Value, String> {
+    match type_tag.unwrap_or("string") {
+        "string" => Ok(serde_json::Value::String(data.to_string())),
+        // Whole-valued input is stored as an integer `Value` rather than a
+        // float, so it round-trips through `as_i64()` (e.g. `Increment`)
+        // instead of silently becoming un-incrementable.
+        "number" => data.parse::<f64>()
+            .map(|n| match n as i64 {
+                i if i as f64 == n => serde_json::json!(i),
+                _ => serde_json::json!(n),
+            })
+            .map_err(|_| "Type 'number' requires Data to be a valid number".to_string()),
+        "bool" => data.parse::<bool>()
+            .map(serde_json::Value::Bool)
+            .map_err(|_| "Type 'bool' requires Data to be 'true' or 'false'".to_string()),
+        "json" => serde_json::from_str(data)
+            .map_err(|e| format!("Invalid JSON value: {}", e)),
+        other => Err(format!("Unknown Type '{}'. Expected string, number, bool, or json", other)),
+    }
+}
+
+// Renders a stored value back out for a response's `Data` field: plain
+// strings are returned as-is, everything else (numbers, bools, arrays,
+// objects) is serialized to its JSON representation.
+fn value_to_response_string(value: &serde_json::Value) -> Result<String, String> {
+    match value {
+        serde_json::Value::String(s) => Ok(s.clone()),
+        other => serde_json::to_string(other).map_err(|e| format!("JSON serialization error: {}", e)),
+    }
+}
+
+// The identity a message is authorized against: `Owner` when present,
+// since that's the signer AO verifies, falling back to `From`.
+fn sender_identity(msg: &AOMessage) -> &str {
+    msg.owner.as_deref().or(msg.from.as_deref()).unwrap_or("unknown")
+}
+
+// AO evaluation must be deterministic across nodes, so "now" is always
+// derived from the incoming message's `Timestamp` rather than a system
+// clock. Messages without a parseable timestamp are treated as if no
+// time has passed, which only affects TTL expiry, never correctness.
+fn parse_timestamp(msg: &AOMessage) -> u64 {
+    msg.timestamp
+        .as_deref()
+        .and_then(|t| t.parse::<u64>().ok())
+        .unwrap_or(0)
+}
+
+// Callbacks registered to run on every cron/tick message, invoked in
+// registration order. Each receives the triggering message and the
+// shared `Outbox`, so it can purge state, emit heartbeat messages, or
+// roll up counters without needing its own entrypoint.
+type CronHandler = fn(&AOMessage, &mut Outbox) -> Result<(), String>;
+static CRON_HANDLERS: LazyLock<Mutex<Vec<CronHandler>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+// Registers a callback to run on every subsequent cron message. Intended
+// to be called once at process startup (e.g. from `init_process`) by
+// code built on top of this template.
+pub fn register_cron_handler(handler: CronHandler) -> Result<(), String> {
+    let mut handlers = CRON_HANDLERS.lock().map_err(|e| format!("State lock error: {}", e))?;
+    handlers.push(handler);
+    Ok(())
+}
+
+// A message is a cron/tick trigger, rather than a sender-initiated
+// action, when AO's scheduler tags it `Action: Cron` or attaches a
+// `Cron` tag alongside the original action, AND it actually originated
+// from the scheduler rather than an ordinary sender merely forging that
+// tag. Without the origin check, any sender could run every registered
+// cron handler on demand, bypassing the admin check that normally gates
+// mutating actions entirely.
+fn is_cron_trigger(msg: &AOMessage) -> bool {
+    let Some(tags) = msg.tags.as_ref() else {
+        return false;
+    };
+    let tagged_as_cron = tags.get("Action").map(|action| action == "Cron").unwrap_or(false) || tags.contains_key("Cron");
+    tagged_as_cron && is_from_scheduler(msg)
+}
+
+// AO's scheduler re-delivers cron messages with the process itself as
+// both sender and target; any message where that doesn't hold was sent
+// by an outside party and must not be treated as a scheduler trigger.
+fn is_from_scheduler(msg: &AOMessage) -> bool {
+    match (msg.from.as_deref(), msg.target.as_deref()) {
+        (Some(from), Some(target)) => from == target,
+        _ => false,
+    }
+}
 
-    ProcessState::clear()?;
+// Entrypoint for scheduled evaluation: runs every registered cron
+// handler in order, accumulating their outbound messages and spawns
+// into a single `HandlerResult`. Registered handlers are expected to do
+// their own state mutation (e.g. via `ProcessState`) and report errors
+// through their `Result`, same as a regular action handler.
+pub fn handle_cron(msg: &AOMessage) -> Result<HandlerResult, String> {
+    let now = parse_timestamp(msg);
+    ProcessState::purge_expired(now)?;
 
-    Ok(AOResponse::new(from, "Clear-Response", "State cleared successfully"))
+    let mut outbox = Outbox::new();
+    let handlers = CRON_HANDLERS.lock().map_err(|e| format!("State lock error: {}", e))?.clone();
+    for handler in &handlers {
+        handler(msg, &mut outbox)?;
+    }
+
+    let from = msg.from.as_deref().unwrap_or("unknown");
+    let output = AOResponse::new(from, "Cron-Response", &format!("Ran {} cron handler(s)", handlers.len()));
+    Ok(HandlerResult::new(Some(output), outbox))
 }
 
 // Main message handler
-pub fn handle_message(msg: &AOMessage) -> Result<AOResponse, String> {
+pub fn handle_message(msg: &AOMessage) -> Result<HandlerResult, String> {
     console_log!("Received message: {:?}", msg);
 
+    let now = parse_timestamp(msg);
+    ProcessState::purge_expired(now)?;
+
+    // Scheduler-driven cron/tick messages bypass the regular action
+    // dispatch below and run the registered cron pipeline instead.
+    if is_cron_trigger(msg) {
+        return handle_cron(msg);
+    }
+
     let action = msg.tags
         .as_ref()
         .and_then(|tags| tags.get("Action"))
         .ok_or("Action is required")?;
 
-    let response = match action.as_str() {
-        "Info" => handle_info(msg)?,
-        "Set" => handle_set(msg)?,
-        "Get" => handle_get(msg)?,
-        "List" => handle_list(msg)?,
-        "Remove" => handle_remove(msg)?,
-        "Clear" => handle_clear(msg)?,
+    let mut outbox = Outbox::new();
+
+    if MUTATING_ACTIONS.contains(&action.as_str()) && !ProcessState::is_admin(sender_identity(msg))? {
+        let from = msg.from.as_deref().unwrap_or("unknown");
+        let output = AOResponse::error(from, "Unauthorized: admin privileges required for this action");
+        return Ok(HandlerResult::new(Some(output), outbox));
+    }
+
+    let output = match action.as_str() {
+        "Info" => handle_info(msg, now, &mut outbox)?,
+        "Set" => handle_set(msg, now, &mut outbox)?,
+        "Get" => handle_get(msg, now, &mut outbox)?,
+        "List" => handle_list(msg, now, &mut outbox)?,
+        "Remove" => handle_remove(msg, now, &mut outbox)?,
+        "Clear" => handle_clear(msg, now, &mut outbox)?,
+        "AddAdmin" => handle_add_admin(msg, now, &mut outbox)?,
+        "RemoveAdmin" => handle_remove_admin(msg, now, &mut outbox)?,
+        "ListNamespaces" => handle_list_namespaces(msg, now, &mut outbox)?,
+        "ConfigureNamespace" => handle_configure_namespace(msg, now, &mut outbox)?,
+        "Increment" => handle_increment(msg, now, &mut outbox)?,
+        "Decrement" => handle_decrement(msg, now, &mut outbox)?,
         _ => {
             let from = msg.from.as_deref().unwrap_or("unknown");
             AOResponse::error(
                 from,
                 &format!(
-                    "Unknown action: {}. Available actions: Info, Set, Get, List, Remove, Clear",
+                    "Unknown action: {}. Available actions: Info, Set, Get, List, Remove, Clear, AddAdmin, RemoveAdmin, ListNamespaces, ConfigureNamespace, Increment, Decrement",
                     action
                 )
             )
         }
     };
 
-    console_log!("Sending response: {:?}", response);
-    Ok(response)
+    console_log!("Sending response: {:?}", output);
+    Ok(HandlerResult::new(Some(output), outbox))
 }
 
 // WebAssembly exports
@@ -246,34 +927,58 @@ pub fn init_process() {
     console_log!("AO Process (Rust) initialized");
 }
 
+// Seeds the admin set from the process's true owner, supplied by the host
+// at instantiation time rather than inferred from the first message it
+// happens to process. Safe to call more than once: a no-op once an admin
+// already exists.
+#[wasm_bindgen]
+pub fn set_owner(owner: &str) -> bool {
+    match ProcessState::bootstrap_owner(owner) {
+        Ok(()) => {
+            console_log!("Owner bootstrapped: {}", owner);
+            true
+        }
+        Err(e) => {
+            console_log!("Error bootstrapping owner: {}", e);
+            false
+        }
+    }
+}
+
 #[wasm_bindgen]
 pub fn handle(message_json: &str) -> String {
     let msg: AOMessage = match serde_json::from_str(message_json) {
         Ok(msg) => msg,
         Err(e) => {
             let error_response = AOResponse::error("unknown", &format!("JSON parse error: {}", e));
-            return serde_json::to_string(&error_response).unwrap_or_else(|_| {
-                r#"{"Target":"unknown","Action":"Error","Data":"Critical JSON error"}"#.to_string()
+            let envelope = Envelope::from_output(error_response);
+            return serde_json::to_string(&envelope).unwrap_or_else(|_| {
+                r#"{"Output":{"Target":"unknown","Action":"Error","Data":"Critical JSON error"},"Messages":[],"Spawns":[]}"#.to_string()
             });
         }
     };
 
-    let response = match handle_message(&msg) {
-        Ok(response) => response,
+    let result = match handle_message(&msg) {
+        Ok(result) => result,
         Err(e) => {
             let from = msg.from.as_deref().unwrap_or("unknown");
-            AOResponse::error(from, &e)
+            HandlerResult::new(Some(AOResponse::error(from, &e)), Outbox::new())
         }
     };
 
-    serde_json::to_string(&response).unwrap_or_else(|_| {
-        r#"{"Target":"unknown","Action":"Error","Data":"Response serialization error"}"#.to_string()
+    let envelope = Envelope::from(result);
+    serde_json::to_string(&envelope).unwrap_or_else(|_| {
+        r#"{"Output":{"Target":"unknown","Action":"Error","Data":"Response serialization error"},"Messages":[],"Spawns":[]}"#.to_string()
     })
 }
 
 #[wasm_bindgen]
 pub fn get_state() -> String {
-    match ProcessState::list() {
+    // No triggering message is available here, so this introspection
+    // export can't advance the deterministic clock; it reports entries
+    // in the default namespace as of the last processed message without
+    // pruning newly-expired ones.
+    match ProcessState::list_at(DEFAULT_NAMESPACE, 0) {
         Ok(state) => serde_json::to_string(&state).unwrap_or_else(|_| "{}".to_string()),
         Err(e) => {
             console_log!("Error getting state: {}", e);
@@ -284,7 +989,7 @@ pub fn get_state() -> String {
 
 #[wasm_bindgen]
 pub fn clear_state() -> bool {
-    match ProcessState::clear() {
+    match ProcessState::clear_all() {
         Ok(()) => {
             console_log!("State cleared");
             true
@@ -296,6 +1001,28 @@ pub fn clear_state() -> bool {
     }
 }
 
+// Checkpoints the full process state (every namespace, TTL metadata, and
+// namespace limits) as a versioned JSON blob the host can persist (e.g.
+// to Arweave) and hand back to `import_state` on cold start.
+#[wasm_bindgen]
+pub fn export_state() -> String {
+    ProcessState::snapshot()
+}
+
+#[wasm_bindgen]
+pub fn import_state(blob: &str) -> bool {
+    match ProcessState::restore(blob) {
+        Ok(()) => {
+            console_log!("State restored from snapshot");
+            true
+        }
+        Err(e) => {
+            console_log!("Error restoring state: {}", e);
+            false
+        }
+    }
+}
+
 // Utility functions for testing
 #[cfg(test)]
 mod tests {
@@ -303,19 +1030,216 @@ mod tests {
 
     #[test]
     fn test_process_state() {
-        ProcessState::clear().unwrap();
+        ProcessState::clear_all().unwrap();
 
         // Test set and get
-        ProcessState::set("test_key", "test_value").unwrap();
-        assert_eq!(ProcessState::get("test_key").unwrap(), Some("test_value".to_string()));
+        ProcessState::set(DEFAULT_NAMESPACE, "test_key", serde_json::json!("test_value"), None).unwrap();
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "test_key", 0).unwrap(), Some(serde_json::json!("test_value")));
 
         // Test list
-        let state = ProcessState::list().unwrap();
-        assert_eq!(state.get("test_key"), Some(&"test_value".to_string()));
+        let state = ProcessState::list_at(DEFAULT_NAMESPACE, 0).unwrap();
+        assert_eq!(state.get("test_key"), Some(&serde_json::json!("test_value")));
 
         // Test remove
-        assert!(ProcessState::remove("test_key").unwrap());
-        assert_eq!(ProcessState::get("test_key").unwrap(), None);
+        assert!(ProcessState::remove(DEFAULT_NAMESPACE, "test_key").unwrap());
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "test_key", 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_ttl_expiry_is_driven_by_message_timestamp() {
+        ProcessState::clear_all().unwrap();
+
+        // Set a key at t=100 with a 10 second TTL -> expires_at = 110.
+        ProcessState::set(DEFAULT_NAMESPACE, "session", serde_json::json!("active"), Some(110)).unwrap();
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "session", 100).unwrap(), Some(serde_json::json!("active")));
+
+        // A later message, timestamped past expiry, no longer sees it.
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "session", 111).unwrap(), None);
+    }
+
+    #[test]
+    fn test_ttl_none_never_expires() {
+        ProcessState::clear_all().unwrap();
+
+        ProcessState::set(DEFAULT_NAMESPACE, "permanent", serde_json::json!("value"), None).unwrap();
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "permanent", u64::MAX).unwrap(), Some(serde_json::json!("value")));
+    }
+
+    #[test]
+    fn test_purge_expired_removes_only_past_entries() {
+        ProcessState::clear_all().unwrap();
+
+        ProcessState::set(DEFAULT_NAMESPACE, "expires-soon", serde_json::json!("a"), Some(50)).unwrap();
+        ProcessState::set(DEFAULT_NAMESPACE, "expires-later", serde_json::json!("b"), Some(200)).unwrap();
+        ProcessState::set(DEFAULT_NAMESPACE, "no-expiry", serde_json::json!("c"), None).unwrap();
+
+        let purged = ProcessState::purge_expired(100).unwrap();
+        assert_eq!(purged, 1);
+
+        let state = ProcessState::list_at(DEFAULT_NAMESPACE, 100).unwrap();
+        assert!(!state.contains_key("expires-soon"));
+        assert!(state.contains_key("expires-later"));
+        assert!(state.contains_key("no-expiry"));
+    }
+
+    #[test]
+    fn test_namespaces_are_isolated() {
+        ProcessState::clear_all().unwrap();
+
+        ProcessState::set("users", "alice", serde_json::json!("admin"), None).unwrap();
+        ProcessState::set("cache", "alice", serde_json::json!("stale"), None).unwrap();
+
+        assert_eq!(ProcessState::get_at("users", "alice", 0).unwrap(), Some(serde_json::json!("admin")));
+        assert_eq!(ProcessState::get_at("cache", "alice", 0).unwrap(), Some(serde_json::json!("stale")));
+
+        ProcessState::clear("cache").unwrap();
+        assert_eq!(ProcessState::get_at("cache", "alice", 0).unwrap(), None);
+        assert_eq!(ProcessState::get_at("users", "alice", 0).unwrap(), Some(serde_json::json!("admin")));
+
+        let namespaces = ProcessState::list_namespaces().unwrap();
+        assert!(namespaces.contains(&"users".to_string()));
+    }
+
+    #[test]
+    fn test_namespace_limits_are_enforced_independently() {
+        ProcessState::clear_all().unwrap();
+
+        ProcessState::configure_namespace("tiny", 1, 1000).unwrap();
+        ProcessState::set("tiny", "first", serde_json::json!("value"), None).unwrap();
+        assert!(ProcessState::set("tiny", "second", serde_json::json!("value"), None).is_err());
+
+        // A differently-configured namespace is unaffected.
+        ProcessState::set("default", "first", serde_json::json!("value"), None).unwrap();
+        ProcessState::set("default", "second", serde_json::json!("value"), None).unwrap();
+    }
+
+    #[test]
+    fn test_set_with_type_tag_stores_structured_values() {
+        ProcessState::clear_all().unwrap();
+        ProcessState::add_admin("test-sender").unwrap();
+
+        let set_number = AOMessage {
+            from: Some("test-sender".to_string()),
+            data: Some("42".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Set".to_string()),
+                ("Key".to_string(), "count".to_string()),
+                ("Type".to_string(), "number".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+        handle_message(&set_number).unwrap();
+        // Whole-valued numbers are stored as integers, not floats, so they
+        // stay incrementable via `ProcessState::increment`'s `as_i64()`.
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "count", 0).unwrap(), Some(serde_json::json!(42)));
+
+        let set_json = AOMessage {
+            from: Some("test-sender".to_string()),
+            data: Some(r#"{"nested":true}"#.to_string()),
+            tags: Some([
+                ("Action".to_string(), "Set".to_string()),
+                ("Key".to_string(), "payload".to_string()),
+                ("Type".to_string(), "json".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+        handle_message(&set_json).unwrap();
+        assert_eq!(
+            ProcessState::get_at(DEFAULT_NAMESPACE, "payload", 0).unwrap(),
+            Some(serde_json::json!({"nested": true}))
+        );
+    }
+
+    #[test]
+    fn test_invalid_json_type_is_rejected() {
+        ProcessState::clear_all().unwrap();
+        ProcessState::add_admin("test-sender").unwrap();
+
+        let set_msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            data: Some("not json".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Set".to_string()),
+                ("Key".to_string(), "broken".to_string()),
+                ("Type".to_string(), "json".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let response = handle_message(&set_msg).unwrap().output.unwrap();
+        assert_eq!(response.action, "Error");
+        assert!(response.data.contains("Invalid JSON value"));
+    }
+
+    #[test]
+    fn test_increment_and_decrement_are_atomic_under_the_lock() {
+        ProcessState::clear_all().unwrap();
+        ProcessState::add_admin("test-sender").unwrap();
+
+        let increment_msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Increment".to_string()),
+                ("Key".to_string(), "balance".to_string()),
+                ("Amount".to_string(), "5".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+        let response = handle_message(&increment_msg).unwrap().output.unwrap();
+        assert_eq!(response.data, "5");
+
+        let decrement_msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Decrement".to_string()),
+                ("Key".to_string(), "balance".to_string()),
+                ("Amount".to_string(), "2".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+        let response = handle_message(&decrement_msg).unwrap().output.unwrap();
+        assert_eq!(response.data, "3");
+    }
+
+    #[test]
+    fn test_increment_works_on_a_key_set_via_type_number() {
+        ProcessState::clear_all().unwrap();
+        ProcessState::add_admin("test-sender").unwrap();
+
+        let set_msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            data: Some("100".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Set".to_string()),
+                ("Key".to_string(), "balance".to_string()),
+                ("Type".to_string(), "number".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+        handle_message(&set_msg).unwrap();
+
+        let increment_msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Increment".to_string()),
+                ("Key".to_string(), "balance".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+        let response = handle_message(&increment_msg).unwrap().output.unwrap();
+        assert_eq!(response.action, "Increment-Response");
+        assert_eq!(response.data, "101");
+    }
+
+    #[test]
+    fn test_increment_errors_instead_of_overflowing() {
+        ProcessState::clear_all().unwrap();
+        ProcessState::set(DEFAULT_NAMESPACE, "maxed-out", serde_json::json!(i64::MAX), None).unwrap();
+
+        let result = ProcessState::increment(DEFAULT_NAMESPACE, "maxed-out", 1, 0);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("overflow"));
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "maxed-out", 0).unwrap(), Some(serde_json::json!(i64::MAX)));
     }
 
     #[test]
@@ -326,27 +1250,274 @@ mod tests {
             ..Default::default()
         };
 
-        let response = handle_info(&msg).unwrap();
+        let mut outbox = Outbox::new();
+        let response = handle_info(&msg, 0, &mut outbox).unwrap();
         assert_eq!(response.action, "Info-Response");
         assert!(response.data.contains("Hello from AO Process (Rust)"));
     }
-}
 
-// Default implementation for AOMessage
-impl Default for AOMessage {
-    fn default() -> Self {
-        Self {
-            id: None,
-            from: None,
-            owner: None,
-            target: None,
-            anchor: None,
-            data: None,
-            tags: None,
-            timestamp: None,
-            block_height: None,
-            hash_chain: None,
-        }
+    #[test]
+    fn test_handle_set_forwards_to_outbox() {
+        ProcessState::clear_all().unwrap();
+
+        let msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            data: Some("test-value".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Set".to_string()),
+                ("Key".to_string(), "test-key".to_string()),
+                ("Forward".to_string(), "subscriber-process".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let mut outbox = Outbox::new();
+        let response = handle_set(&msg, 0, &mut outbox).unwrap();
+        assert_eq!(response.action, "Set-Response");
+        assert_eq!(outbox.messages.len(), 1);
+        assert_eq!(outbox.messages[0].target, "subscriber-process");
+        assert_eq!(outbox.messages[0].data, "test-value");
     }
-}
 
+    #[test]
+    fn test_set_with_huge_ttl_errors_instead_of_overflowing() {
+        ProcessState::clear_all().unwrap();
+
+        let msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            data: Some("value".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Set".to_string()),
+                ("Key".to_string(), "overflow-key".to_string()),
+                ("Ttl".to_string(), u64::MAX.to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let mut outbox = Outbox::new();
+        let result = handle_set(&msg, 1, &mut outbox);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("overflow"));
+    }
+
+    #[test]
+    fn test_handle_message_envelope() {
+        ProcessState::clear_all().unwrap();
+
+        let msg = AOMessage {
+            from: Some("test-sender".to_string()),
+            tags: Some([("Action".to_string(), "Info".to_string())].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let result = handle_message(&msg).unwrap();
+        assert!(result.output.is_some());
+        assert!(result.messages.is_empty());
+        assert!(result.spawns.is_empty());
+    }
+
+    #[test]
+    fn test_unauthorized_sender_cannot_set() {
+        ProcessState::clear_all().unwrap();
+
+        let msg = AOMessage {
+            from: Some("not-an-admin".to_string()),
+            data: Some("test-value".to_string()),
+            tags: Some([
+                ("Action".to_string(), "Set".to_string()),
+                ("Key".to_string(), "test-key".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let response = handle_message(&msg).unwrap().output.unwrap();
+        assert_eq!(response.action, "Error");
+        assert!(response.data.contains("Unauthorized"));
+        assert_eq!(ProcessState::get_at(DEFAULT_NAMESPACE, "test-key", 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_add_and_remove_admin() {
+        ProcessState::add_admin("root-admin").unwrap();
+        assert!(ProcessState::is_admin("root-admin").unwrap());
+
+        let add_msg = AOMessage {
+            from: Some("root-admin".to_string()),
+            tags: Some([
+                ("Action".to_string(), "AddAdmin".to_string()),
+                ("Address".to_string(), "new-admin".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let response = handle_message(&add_msg).unwrap().output.unwrap();
+        assert_eq!(response.action, "AddAdmin-Response");
+        assert!(ProcessState::is_admin("new-admin").unwrap());
+
+        let remove_msg = AOMessage {
+            from: Some("root-admin".to_string()),
+            tags: Some([
+                ("Action".to_string(), "RemoveAdmin".to_string()),
+                ("Address".to_string(), "new-admin".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let response = handle_message(&remove_msg).unwrap().output.unwrap();
+        assert_eq!(response.action, "RemoveAdmin-Response");
+        assert!(!ProcessState::is_admin("new-admin").unwrap());
+    }
+
+    #[test]
+    fn test_handle_message_never_bootstraps_owner_from_a_message() {
+        ProcessState::reset_admins_for_test().unwrap();
+        let attacker_msg = AOMessage {
+            from: Some("attacker".to_string()),
+            owner: Some("attacker".to_string()),
+            tags: Some([("Action".to_string(), "Info".to_string())].iter().cloned().collect()),
+            ..Default::default()
+        };
+        handle_message(&attacker_msg).unwrap();
+        assert!(!ProcessState::is_admin("attacker").unwrap());
+
+        assert!(ProcessState::bootstrap_owner("real-owner").is_ok());
+        assert!(ProcessState::is_admin("real-owner").unwrap());
+    }
+
+    #[test]
+    fn test_cannot_remove_the_last_remaining_admin() {
+        ProcessState::reset_admins_for_test().unwrap();
+        ProcessState::add_admin("sole-admin").unwrap();
+
+        let result = ProcessState::remove_admin("sole-admin");
+        assert!(result.is_err());
+        assert!(ProcessState::is_admin("sole-admin").unwrap());
+
+        ProcessState::add_admin("second-admin").unwrap();
+        assert!(ProcessState::remove_admin("sole-admin").unwrap());
+        assert!(!ProcessState::is_admin("sole-admin").unwrap());
+    }
+
+    fn heartbeat_cron_handler(msg: &AOMessage, outbox: &mut Outbox) -> Result<(), String> {
+        let mut tags = HashMap::new();
+        tags.insert("Action".to_string(), "Heartbeat".to_string());
+        outbox.send(msg.target.as_deref().unwrap_or("self"), tags, "tick");
+        Ok(())
+    }
+
+    #[test]
+    fn test_cron_message_runs_registered_handlers() {
+        register_cron_handler(heartbeat_cron_handler).unwrap();
+
+        let cron_msg = AOMessage {
+            from: Some("process-id".to_string()),
+            target: Some("process-id".to_string()),
+            tags: Some([("Action".to_string(), "Cron".to_string())].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        let result = handle_message(&cron_msg).unwrap();
+        assert_eq!(result.output.unwrap().action, "Cron-Response");
+        assert!(result.messages.iter().any(|m| m.data == "tick"));
+    }
+
+    #[test]
+    fn test_cron_tag_from_an_ordinary_sender_is_not_a_cron_trigger() {
+        ProcessState::clear_all().unwrap();
+
+        let forged_msg = AOMessage {
+            from: Some("attacker".to_string()),
+            target: Some("process-id".to_string()),
+            tags: Some([("Action".to_string(), "Cron".to_string())].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        assert!(!is_cron_trigger(&forged_msg));
+
+        // Falls through to normal action dispatch, where "Cron" isn't a
+        // recognized action, rather than silently running cron handlers.
+        let response = handle_message(&forged_msg).unwrap().output.unwrap();
+        assert_eq!(response.action, "Error");
+        assert!(response.data.contains("Unknown action"));
+    }
+
+    #[test]
+    fn test_snapshot_restore_round_trips_namespaces_and_ttl() {
+        ProcessState::clear_all().unwrap();
+        ProcessState::add_admin("owner-address").unwrap();
+
+        ProcessState::configure_namespace("users", 10, 500).unwrap();
+        ProcessState::set("users", "alice", serde_json::json!("admin"), None).unwrap();
+        ProcessState::set("users", "session", serde_json::json!("token"), Some(200)).unwrap();
+
+        let blob = ProcessState::snapshot();
+
+        ProcessState::clear_all().unwrap();
+        // `remove_admin` refuses to remove the last admin, so make sure
+        // "owner-address" isn't the only one before removing it.
+        ProcessState::add_admin("placeholder-admin").unwrap();
+        ProcessState::remove_admin("owner-address").unwrap();
+        assert_eq!(ProcessState::get_at("users", "alice", 0).unwrap(), None);
+        assert!(!ProcessState::is_admin("owner-address").unwrap());
+
+        ProcessState::restore(&blob).unwrap();
+        assert_eq!(ProcessState::get_at("users", "alice", 0).unwrap(), Some(serde_json::json!("admin")));
+        // TTL metadata survived the round trip too.
+        assert_eq!(ProcessState::get_at("users", "session", 100).unwrap(), Some(serde_json::json!("token")));
+        assert_eq!(ProcessState::get_at("users", "session", 200).unwrap(), None);
+
+        let limits = ProcessState::namespace_limits("users").unwrap();
+        assert_eq!(limits.max_entries, 10);
+        assert_eq!(limits.max_value_len, 500);
+
+        // The admin set survives the round trip too, so a cold start
+        // followed by `import_state` keeps the same admins instead of
+        // leaving `ADMINS` empty.
+        assert!(ProcessState::is_admin("owner-address").unwrap());
+    }
+
+    #[test]
+    fn test_restore_rejects_future_snapshot_version() {
+        let future_blob = serde_json::json!({
+            "Version": SNAPSHOT_FORMAT_VERSION + 1,
+            "Namespaces": {},
+            "NamespaceLimits": {},
+            "Admins": [],
+        }).to_string();
+
+        let result = ProcessState::restore(&future_blob);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Unsupported snapshot version"));
+    }
+
+    #[test]
+    fn test_restore_defaults_admins_for_a_pre_admin_snapshot() {
+        ProcessState::clear_all().unwrap();
+        ProcessState::add_admin("stale-admin").unwrap();
+
+        let old_blob = serde_json::json!({
+            "Version": 1,
+            "Namespaces": {"default": {"key": ["value", null]}},
+            "NamespaceLimits": {},
+        }).to_string();
+
+        ProcessState::restore(&old_blob).unwrap();
+        assert_eq!(ProcessState::get_at("default", "key", 0).unwrap(), Some(serde_json::json!("value")));
+        assert!(!ProcessState::is_admin("stale-admin").unwrap());
+    }
+
+    #[test]
+    fn test_cron_trigger_detected_via_cron_tag() {
+        let msg = AOMessage {
+            from: Some("process-id".to_string()),
+            target: Some("process-id".to_string()),
+            tags: Some([
+                ("Action".to_string(), "SomeOtherAction".to_string()),
+                ("Cron".to_string(), "true".to_string()),
+            ].iter().cloned().collect()),
+            ..Default::default()
+        };
+
+        assert!(is_cron_trigger(&msg));
+    }
+}
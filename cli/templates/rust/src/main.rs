@@ -6,6 +6,10 @@ fn main() {
     println!("🎭 AO Process (Rust) - Testing Mode");
     println!("=====================================");
 
+    // Bootstrap the test sender as an admin so the mutating actions
+    // below aren't rejected as unauthorized.
+    ProcessState::add_admin("test-sender").unwrap();
+
     // Test the Info action
     println!("\n1. Testing Info action:");
     let info_msg = AOMessage {
@@ -15,8 +19,8 @@ fn main() {
     };
 
     match handle_message(&info_msg) {
-        Ok(response) => {
-            let json = serde_json::to_string_pretty(&response).unwrap();
+        Ok(result) => {
+            let json = serde_json::to_string_pretty(&result.output).unwrap();
             println!("Response: {}", json);
         }
         Err(e) => println!("Error: {}", e),
@@ -35,8 +39,8 @@ fn main() {
     };
 
     match handle_message(&set_msg) {
-        Ok(response) => {
-            let json = serde_json::to_string_pretty(&response).unwrap();
+        Ok(result) => {
+            let json = serde_json::to_string_pretty(&result.output).unwrap();
             println!("Response: {}", json);
         }
         Err(e) => println!("Error: {}", e),
@@ -54,8 +58,8 @@ fn main() {
     };
 
     match handle_message(&get_msg) {
-        Ok(response) => {
-            let json = serde_json::to_string_pretty(&response).unwrap();
+        Ok(result) => {
+            let json = serde_json::to_string_pretty(&result.output).unwrap();
             println!("Response: {}", json);
         }
         Err(e) => println!("Error: {}", e),
@@ -70,8 +74,8 @@ fn main() {
     };
 
     match handle_message(&list_msg) {
-        Ok(response) => {
-            let json = serde_json::to_string_pretty(&response).unwrap();
+        Ok(result) => {
+            let json = serde_json::to_string_pretty(&result.output).unwrap();
             println!("Response: {}", json);
         }
         Err(e) => println!("Error: {}", e),
@@ -86,8 +90,8 @@ fn main() {
     };
 
     match handle_message(&error_msg) {
-        Ok(response) => {
-            let json = serde_json::to_string_pretty(&response).unwrap();
+        Ok(result) => {
+            let json = serde_json::to_string_pretty(&result.output).unwrap();
             println!("Response: {}", json);
         }
         Err(e) => println!("Error: {}", e),
@@ -97,13 +101,13 @@ fn main() {
     println!("\n6. Testing direct state operations:");
 
     // Set multiple values
-    ProcessState::set("name", "Alice").unwrap();
-    ProcessState::set("age", "30").unwrap();
-    ProcessState::set("city", "New York").unwrap();
+    ProcessState::set("default", "name", serde_json::json!("Alice"), None).unwrap();
+    ProcessState::set("default", "age", serde_json::json!("30"), None).unwrap();
+    ProcessState::set("default", "city", serde_json::json!("New York"), None).unwrap();
 
-    println!("State size: {}", ProcessState::size().unwrap());
+    println!("State size: {}", ProcessState::size_at("default", 0).unwrap());
 
-    let state = ProcessState::list().unwrap();
+    let state = ProcessState::list_at("default", 0).unwrap();
     println!("Current state: {}", serde_json::to_string_pretty(&state).unwrap());
 
     // Test Remove action
@@ -118,8 +122,8 @@ fn main() {
     };
 
     match handle_message(&remove_msg) {
-        Ok(response) => {
-            let json = serde_json::to_string_pretty(&response).unwrap();
+        Ok(result) => {
+            let json = serde_json::to_string_pretty(&result.output).unwrap();
             println!("Response: {}", json);
         }
         Err(e) => println!("Error: {}", e),
@@ -134,8 +138,8 @@ fn main() {
     };
 
     match handle_message(&clear_msg) {
-        Ok(response) => {
-            let json = serde_json::to_string_pretty(&response).unwrap();
+        Ok(result) => {
+            let json = serde_json::to_string_pretty(&result.output).unwrap();
             println!("Response: {}", json);
         }
         Err(e) => println!("Error: {}", e),
@@ -143,4 +147,3 @@ fn main() {
 
     println!("\n✅ All tests completed!");
 }
-
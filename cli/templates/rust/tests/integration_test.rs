@@ -10,7 +10,8 @@ fn test_info_handler() {
         ..Default::default()
     };
 
-    let response = handle_message(&msg).unwrap();
+    let result = handle_message(&msg).unwrap();
+    let response = result.output.unwrap();
     assert_eq!(response.action, "Info-Response");
     assert_eq!(response.target, "test-sender");
     assert!(response.data.contains("Hello from AO Process (Rust)"));
@@ -19,7 +20,8 @@ fn test_info_handler() {
 #[test]
 fn test_set_and_get_handlers() {
     // Clear state first
-    ProcessState::clear().unwrap();
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
 
     // Test Set
     let set_msg = AOMessage {
@@ -32,9 +34,11 @@ fn test_set_and_get_handlers() {
         ..Default::default()
     };
 
-    let set_response = handle_message(&set_msg).unwrap();
+    let set_result = handle_message(&set_msg).unwrap();
+    let set_response = set_result.output.unwrap();
     assert_eq!(set_response.action, "Set-Response");
     assert!(set_response.data.contains("Successfully set test-key to test-value"));
+    assert!(set_result.messages.is_empty());
 
     // Test Get
     let get_msg = AOMessage {
@@ -46,18 +50,42 @@ fn test_set_and_get_handlers() {
         ..Default::default()
     };
 
-    let get_response = handle_message(&get_msg).unwrap();
+    let get_result = handle_message(&get_msg).unwrap();
+    let get_response = get_result.output.unwrap();
     assert_eq!(get_response.action, "Get-Response");
     assert_eq!(get_response.data, "test-value");
     assert_eq!(get_response.extra_fields.get("Key"), Some(&"test-key".to_string()));
 }
 
+#[test]
+fn test_set_handler_forwards_outbound_message() {
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+
+    let set_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        data: Some("test-value".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "test-key".to_string()),
+            ("Forward".to_string(), "subscriber-process".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+
+    let result = handle_message(&set_msg).unwrap();
+    assert_eq!(result.messages.len(), 1);
+    assert_eq!(result.messages[0].target, "subscriber-process");
+    assert_eq!(result.messages[0].data, "test-value");
+    assert!(result.spawns.is_empty());
+}
+
 #[test]
 fn test_list_handler() {
     // Clear state and add some test data
-    ProcessState::clear().unwrap();
-    ProcessState::set("key1", "value1").unwrap();
-    ProcessState::set("key2", "value2").unwrap();
+    ProcessState::clear_all().unwrap();
+    ProcessState::set("default", "key1", serde_json::json!("value1"), None).unwrap();
+    ProcessState::set("default", "key2", serde_json::json!("value2"), None).unwrap();
 
     let list_msg = AOMessage {
         from: Some("test-sender".to_string()),
@@ -65,19 +93,21 @@ fn test_list_handler() {
         ..Default::default()
     };
 
-    let response = handle_message(&list_msg).unwrap();
+    let result = handle_message(&list_msg).unwrap();
+    let response = result.output.unwrap();
     assert_eq!(response.action, "List-Response");
 
-    let state: HashMap<String, String> = serde_json::from_str(&response.data).unwrap();
-    assert_eq!(state.get("key1"), Some(&"value1".to_string()));
-    assert_eq!(state.get("key2"), Some(&"value2".to_string()));
+    let state: HashMap<String, serde_json::Value> = serde_json::from_str(&response.data).unwrap();
+    assert_eq!(state.get("key1"), Some(&serde_json::json!("value1")));
+    assert_eq!(state.get("key2"), Some(&serde_json::json!("value2")));
 }
 
 #[test]
 fn test_remove_handler() {
     // Clear state and add test data
-    ProcessState::clear().unwrap();
-    ProcessState::set("test-key", "test-value").unwrap();
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+    ProcessState::set("default", "test-key", serde_json::json!("test-value"), None).unwrap();
 
     let remove_msg = AOMessage {
         from: Some("test-sender".to_string()),
@@ -88,19 +118,21 @@ fn test_remove_handler() {
         ..Default::default()
     };
 
-    let response = handle_message(&remove_msg).unwrap();
+    let result = handle_message(&remove_msg).unwrap();
+    let response = result.output.unwrap();
     assert_eq!(response.action, "Remove-Response");
     assert!(response.data.contains("Successfully removed test-key"));
 
     // Verify the key is actually removed
-    assert_eq!(ProcessState::get("test-key").unwrap(), None);
+    assert_eq!(ProcessState::get_at("default", "test-key", 0).unwrap(), None);
 }
 
 #[test]
 fn test_clear_handler() {
     // Add some test data
-    ProcessState::set("key1", "value1").unwrap();
-    ProcessState::set("key2", "value2").unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+    ProcessState::set("default", "key1", serde_json::json!("value1"), None).unwrap();
+    ProcessState::set("default", "key2", serde_json::json!("value2"), None).unwrap();
 
     let clear_msg = AOMessage {
         from: Some("test-sender".to_string()),
@@ -108,25 +140,114 @@ fn test_clear_handler() {
         ..Default::default()
     };
 
-    let response = handle_message(&clear_msg).unwrap();
+    let result = handle_message(&clear_msg).unwrap();
+    let response = result.output.unwrap();
     assert_eq!(response.action, "Clear-Response");
-    assert_eq!(response.data, "State cleared successfully");
+    assert_eq!(response.data, "Namespace 'default' cleared successfully");
 
     // Verify state is actually cleared
-    assert_eq!(ProcessState::size().unwrap(), 0);
+    assert_eq!(ProcessState::size_at("default", 0).unwrap(), 0);
+}
+
+#[test]
+fn test_unauthorized_sender_cannot_mutate_state() {
+    ProcessState::clear_all().unwrap();
+
+    let set_msg = AOMessage {
+        from: Some("stranger".to_string()),
+        data: Some("test-value".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "test-key".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+
+    let response = handle_message(&set_msg).unwrap().output.unwrap();
+    assert_eq!(response.action, "Error");
+    assert!(response.data.contains("Unauthorized"));
+    assert_eq!(ProcessState::get_at("default", "test-key", 0).unwrap(), None);
+}
+
+#[test]
+fn test_admin_can_mutate_state() {
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("owner-wallet").unwrap();
+
+    let set_msg = AOMessage {
+        from: Some("owner-wallet".to_string()),
+        owner: Some("owner-wallet".to_string()),
+        data: Some("test-value".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "owner-key".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+
+    let response = handle_message(&set_msg).unwrap().output.unwrap();
+    assert_eq!(response.action, "Set-Response");
+    assert_eq!(ProcessState::get_at("default", "owner-key", 0).unwrap(), Some(serde_json::json!("test-value")));
+}
+
+#[test]
+fn test_set_with_ttl_expires_on_later_timestamped_message() {
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+
+    let set_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        data: Some("ephemeral".to_string()),
+        timestamp: Some("100".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "session".to_string()),
+            ("Ttl".to_string(), "10".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    handle_message(&set_msg).unwrap();
+
+    // Still visible just before expiry.
+    let get_before = AOMessage {
+        from: Some("test-sender".to_string()),
+        timestamp: Some("109".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Get".to_string()),
+            ("Key".to_string(), "session".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&get_before).unwrap().output.unwrap();
+    assert_eq!(response.data, "ephemeral");
+
+    // Gone once a later message arrives past expiry.
+    let get_after = AOMessage {
+        from: Some("test-sender".to_string()),
+        timestamp: Some("110".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Get".to_string()),
+            ("Key".to_string(), "session".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&get_after).unwrap().output.unwrap();
+    assert_eq!(response.data, "Not found");
 }
 
 #[test]
 fn test_error_handling() {
+    ProcessState::add_admin("test-sender").unwrap();
+
     // Test missing action
     let msg_no_action = AOMessage {
         from: Some("test-sender".to_string()),
         ..Default::default()
     };
 
-    let response = handle_message(&msg_no_action).unwrap();
-    assert_eq!(response.action, "Error");
-    assert!(response.data.contains("Action is required"));
+    let result = handle_message(&msg_no_action);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("Action is required"));
 
     // Test unknown action
     let msg_unknown_action = AOMessage {
@@ -135,7 +256,7 @@ fn test_error_handling() {
         ..Default::default()
     };
 
-    let response = handle_message(&msg_unknown_action).unwrap();
+    let response = handle_message(&msg_unknown_action).unwrap().output.unwrap();
     assert_eq!(response.action, "Error");
     assert!(response.data.contains("Unknown action: UnknownAction"));
 
@@ -147,13 +268,15 @@ fn test_error_handling() {
         ..Default::default()
     };
 
-    let response = handle_message(&msg_no_key).unwrap();
-    assert_eq!(response.action, "Error");
-    assert!(response.data.contains("Key is required"));
+    let result = handle_message(&msg_no_key);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("Key is required"));
 }
 
 #[test]
 fn test_key_validation() {
+    ProcessState::add_admin("test-sender").unwrap();
+
     let msg_invalid_key = AOMessage {
         from: Some("test-sender".to_string()),
         data: Some("test-value".to_string()),
@@ -164,36 +287,36 @@ fn test_key_validation() {
         ..Default::default()
     };
 
-    let response = handle_message(&msg_invalid_key).unwrap();
+    let response = handle_message(&msg_invalid_key).unwrap().output.unwrap();
     assert_eq!(response.action, "Error");
     assert!(response.data.contains("Invalid key format"));
 }
 
 #[test]
 fn test_state_operations() {
-    ProcessState::clear().unwrap();
+    ProcessState::clear_all().unwrap();
 
     // Test basic operations
-    assert_eq!(ProcessState::size().unwrap(), 0);
+    assert_eq!(ProcessState::size_at("default", 0).unwrap(), 0);
 
-    ProcessState::set("test", "value").unwrap();
-    assert_eq!(ProcessState::size().unwrap(), 1);
-    assert_eq!(ProcessState::get("test").unwrap(), Some("value".to_string()));
+    ProcessState::set("default", "test", serde_json::json!("value"), None).unwrap();
+    assert_eq!(ProcessState::size_at("default", 0).unwrap(), 1);
+    assert_eq!(ProcessState::get_at("default", "test", 0).unwrap(), Some(serde_json::json!("value")));
 
-    let state = ProcessState::list().unwrap();
+    let state = ProcessState::list_at("default", 0).unwrap();
     assert_eq!(state.len(), 1);
-    assert_eq!(state.get("test"), Some(&"value".to_string()));
+    assert_eq!(state.get("test"), Some(&serde_json::json!("value")));
 
-    assert!(ProcessState::remove("test").unwrap());
-    assert!(!ProcessState::remove("nonexistent").unwrap());
-    assert_eq!(ProcessState::size().unwrap(), 0);
+    assert!(ProcessState::remove("default", "test").unwrap());
+    assert!(!ProcessState::remove("default", "nonexistent").unwrap());
+    assert_eq!(ProcessState::size_at("default", 0).unwrap(), 0);
 }
 
 #[test]
 fn test_value_size_limits() {
     let large_value = "x".repeat(1001); // Exceeds 1000 character limit
 
-    let result = ProcessState::set("test", &large_value);
+    let result = ProcessState::set("default", "test", serde_json::json!(large_value), None);
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("Value must be less than 1000 characters"));
 }
@@ -202,23 +325,179 @@ fn test_value_size_limits() {
 fn test_key_size_limits() {
     let large_key = "x".repeat(65); // Exceeds 64 character limit
 
-    let result = ProcessState::set(&large_key, "value");
+    let result = ProcessState::set("default", &large_key, serde_json::json!("value"), None);
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("Key must be between 1 and 64 characters"));
 
     // Test empty key
-    let result = ProcessState::set("", "value");
+    let result = ProcessState::set("default", "", serde_json::json!("value"), None);
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("Key must be between 1 and 64 characters"));
 }
 
+#[test]
+fn test_namespace_isolation_through_handlers() {
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+
+    let set_users = AOMessage {
+        from: Some("test-sender".to_string()),
+        data: Some("alice".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "name".to_string()),
+            ("Namespace".to_string(), "users".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    handle_message(&set_users).unwrap();
+
+    let get_default = AOMessage {
+        from: Some("test-sender".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Get".to_string()),
+            ("Key".to_string(), "name".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&get_default).unwrap().output.unwrap();
+    assert_eq!(response.data, "Not found");
+
+    let get_users = AOMessage {
+        from: Some("test-sender".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Get".to_string()),
+            ("Key".to_string(), "name".to_string()),
+            ("Namespace".to_string(), "users".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&get_users).unwrap().output.unwrap();
+    assert_eq!(response.data, "alice");
+
+    let list_namespaces_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        tags: Some([("Action".to_string(), "ListNamespaces".to_string())].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&list_namespaces_msg).unwrap().output.unwrap();
+    let namespaces: Vec<String> = serde_json::from_str(&response.data).unwrap();
+    assert!(namespaces.contains(&"users".to_string()));
+}
+
+#[test]
+fn test_configure_namespace_enforces_limits() {
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+
+    let configure_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        tags: Some([
+            ("Action".to_string(), "ConfigureNamespace".to_string()),
+            ("Namespace".to_string(), "limited".to_string()),
+            ("MaxEntries".to_string(), "1".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&configure_msg).unwrap().output.unwrap();
+    assert_eq!(response.action, "ConfigureNamespace-Response");
+
+    let first_set = AOMessage {
+        from: Some("test-sender".to_string()),
+        data: Some("one".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "a".to_string()),
+            ("Namespace".to_string(), "limited".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&first_set).unwrap().output.unwrap();
+    assert_eq!(response.action, "Set-Response");
+
+    let second_set = AOMessage {
+        from: Some("test-sender".to_string()),
+        data: Some("two".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "b".to_string()),
+            ("Namespace".to_string(), "limited".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let result = handle_message(&second_set);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("reached its limit"));
+}
+
+#[test]
+fn test_set_with_type_tag_round_trips_structured_values() {
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+
+    let set_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        data: Some("true".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Set".to_string()),
+            ("Key".to_string(), "feature-flag".to_string()),
+            ("Type".to_string(), "bool".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    handle_message(&set_msg).unwrap();
+
+    let get_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Get".to_string()),
+            ("Key".to_string(), "feature-flag".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&get_msg).unwrap().output.unwrap();
+    assert_eq!(response.data, "true");
+}
+
+#[test]
+fn test_increment_and_decrement_handlers() {
+    ProcessState::clear_all().unwrap();
+    ProcessState::add_admin("test-sender").unwrap();
+
+    let increment_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Increment".to_string()),
+            ("Key".to_string(), "visits".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    handle_message(&increment_msg).unwrap();
+    let response = handle_message(&increment_msg).unwrap().output.unwrap();
+    assert_eq!(response.action, "Increment-Response");
+    assert_eq!(response.data, "2");
+
+    let decrement_msg = AOMessage {
+        from: Some("test-sender".to_string()),
+        tags: Some([
+            ("Action".to_string(), "Decrement".to_string()),
+            ("Key".to_string(), "visits".to_string()),
+            ("Amount".to_string(), "2".to_string()),
+        ].iter().cloned().collect()),
+        ..Default::default()
+    };
+    let response = handle_message(&decrement_msg).unwrap().output.unwrap();
+    assert_eq!(response.action, "Decrement-Response");
+    assert_eq!(response.data, "0");
+}
+
 #[test]
 fn test_concurrent_operations() {
     use std::thread;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
-    ProcessState::clear().unwrap();
+    ProcessState::clear_all().unwrap();
     let counter = Arc::new(AtomicUsize::new(0));
     let mut handles = vec![];
 
@@ -229,7 +508,7 @@ fn test_concurrent_operations() {
             let key = format!("key{}", i);
             let value = format!("value{}", i);
 
-            ProcessState::set(&key, &value).unwrap();
+            ProcessState::set("default", &key, serde_json::json!(value), None).unwrap();
             counter_clone.fetch_add(1, Ordering::SeqCst);
         });
         handles.push(handle);
@@ -241,6 +520,5 @@ fn test_concurrent_operations() {
     }
 
     assert_eq!(counter.load(Ordering::SeqCst), 10);
-    assert_eq!(ProcessState::size().unwrap(), 10);
+    assert_eq!(ProcessState::size_at("default", 0).unwrap(), 10);
 }
-